@@ -0,0 +1,46 @@
+/// Fields of the kernel's global metadata segment, addressed by the asm
+/// constants `@GLOBAL_METADATA_*` (e.g. `@GLOBAL_METADATA_STATE_TRIE_ROOT`
+/// resolves to `GlobalMetadata::StateTrieRoot as usize`).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum GlobalMetadata {
+    /// Number of words written so far to `@SEGMENT_TRIE_DATA`; used as a
+    /// bump allocator when new nodes are appended (see `load_mpt`,
+    /// `mpt_delete`'s fuse/collapse helpers).
+    TrieDataSize = 0,
+    /// Pointer to the root of the currently loaded state trie.
+    StateTrieRoot = 1,
+    /// Pointer to the root of the currently loaded transactions trie.
+    TxnTrieRoot = 2,
+    /// Pointer to the root of the currently loaded receipts trie.
+    ReceiptTrieRoot = 3,
+    /// The pre-state root asserted by the host when loading the state
+    /// trie from a witness (`load_mpts_from_witness`), checked against
+    /// the root actually reconstructed from the supplied proof nodes.
+    StateTrieRootAsserted = 4,
+}
+
+impl GlobalMetadata {
+    pub(crate) const COUNT: usize = 5;
+
+    pub(crate) fn all() -> [Self; Self::COUNT] {
+        [
+            Self::TrieDataSize,
+            Self::StateTrieRoot,
+            Self::TxnTrieRoot,
+            Self::ReceiptTrieRoot,
+            Self::StateTrieRootAsserted,
+        ]
+    }
+
+    /// The variable name used for this field's offset in the kernel
+    /// assembly, e.g. `GLOBAL_METADATA_STATE_TRIE_ROOT`.
+    pub(crate) fn var_name(&self) -> &'static str {
+        match self {
+            Self::TrieDataSize => "GLOBAL_METADATA_TRIE_DATA_SIZE",
+            Self::StateTrieRoot => "GLOBAL_METADATA_STATE_TRIE_ROOT",
+            Self::TxnTrieRoot => "GLOBAL_METADATA_TXN_TRIE_ROOT",
+            Self::ReceiptTrieRoot => "GLOBAL_METADATA_RECEIPT_TRIE_ROOT",
+            Self::StateTrieRootAsserted => "GLOBAL_METADATA_STATE_TRIE_ROOT_ASSERTED",
+        }
+    }
+}
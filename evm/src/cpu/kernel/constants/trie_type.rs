@@ -0,0 +1,24 @@
+/// The node-type tag stored as the first `trie_data` word of every MPT
+/// node, mirroring the `MPT_NODE_*` constants used by the kernel assembly
+/// (see `asm/mpt/load.asm`, `asm/mpt/hash.asm`, `asm/mpt/delete.asm`).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum PartialTrieType {
+    Empty = 0,
+    Hash = 1,
+    Branch = 2,
+    Extension = 3,
+    Leaf = 4,
+}
+
+impl PartialTrieType {
+    pub(crate) fn from_u32(tag: u32) -> Self {
+        match tag {
+            0 => Self::Empty,
+            1 => Self::Hash,
+            2 => Self::Branch,
+            3 => Self::Extension,
+            4 => Self::Leaf,
+            _ => panic!("Invalid MPT node type tag: {}", tag),
+        }
+    }
+}
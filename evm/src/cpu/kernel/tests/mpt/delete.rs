@@ -0,0 +1,165 @@
+use anyhow::Result;
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{BigEndianHash, H256};
+
+use super::diff::diff_tries;
+use super::nibbles;
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::mpt::{test_account_1_rlp, test_account_2_rlp};
+use crate::generation::mpt::all_mpt_prover_inputs_reversed;
+use crate::generation::TrieInputs;
+
+#[test]
+fn mpt_delete_only_key() -> Result<()> {
+    let key = nibbles(0xABC);
+    let state_trie = PartialTrie::Leaf {
+        nibbles: key,
+        value: test_account_1_rlp(),
+    };
+    test_state_trie_delete(state_trie, key)
+}
+
+#[test]
+fn mpt_delete_branch_to_leaf() -> Result<()> {
+    // Deleting the leaf at nibble 0xA leaves a single child at 0xB, so the
+    // branch collapses into that child with 0xB prepended to its key.
+    let mut children = std::array::from_fn(|_| PartialTrie::Empty.into());
+    children[0xA] = PartialTrie::Leaf {
+        nibbles: nibbles(0xBC),
+        value: test_account_1_rlp(),
+    }
+    .into();
+    children[0xB] = PartialTrie::Leaf {
+        nibbles: nibbles(0xCD),
+        value: test_account_2_rlp(),
+    }
+    .into();
+    let state_trie = PartialTrie::Branch {
+        children,
+        value: vec![],
+    };
+
+    test_state_trie_delete(state_trie, nibbles(0xABC))
+}
+
+#[test]
+fn mpt_delete_branch_value_keeps_children() -> Result<()> {
+    // The branch has a value and two children; deleting the value leaves
+    // the branch (with both children) intact.
+    let mut children = std::array::from_fn(|_| PartialTrie::Empty.into());
+    children[0xA] = PartialTrie::Leaf {
+        nibbles: nibbles(0xBC),
+        value: test_account_1_rlp(),
+    }
+    .into();
+    children[0xB] = PartialTrie::Leaf {
+        nibbles: nibbles(0xCD),
+        value: test_account_2_rlp(),
+    }
+    .into();
+    let state_trie = PartialTrie::Branch {
+        children,
+        value: test_account_1_rlp(),
+    };
+
+    // An empty key targets the branch's own value.
+    test_state_trie_delete(state_trie, Nibbles::default())
+}
+
+#[test]
+fn mpt_delete_fuses_extension_and_leaf() -> Result<()> {
+    // Existing keys are 0xABC and 0xABD; deleting 0xABD leaves a single
+    // child under the branch, which must fuse with the enclosing
+    // extension into a single leaf with key 0xABC.
+    let mut children = std::array::from_fn(|_| PartialTrie::Empty.into());
+    children[0xC] = PartialTrie::Leaf {
+        nibbles: Nibbles::default(),
+        value: test_account_1_rlp(),
+    }
+    .into();
+    children[0xD] = PartialTrie::Leaf {
+        nibbles: Nibbles::default(),
+        value: test_account_2_rlp(),
+    }
+    .into();
+    let state_trie = PartialTrie::Extension {
+        nibbles: nibbles(0xAB),
+        child: PartialTrie::Branch {
+            children,
+            value: vec![],
+        }
+        .into(),
+    };
+
+    test_state_trie_delete(state_trie, nibbles(0xABD))
+}
+
+#[test]
+fn mpt_delete_nonexistent_key_is_noop() -> Result<()> {
+    let state_trie = PartialTrie::Leaf {
+        nibbles: nibbles(0xABC),
+        value: test_account_1_rlp(),
+    };
+    test_state_trie_delete(state_trie, nibbles(0x123))
+}
+
+fn test_state_trie_delete(state_trie: PartialTrie, k: Nibbles) -> Result<()> {
+    let trie_inputs = TrieInputs {
+        state_trie: state_trie.clone(),
+        transactions_trie: Default::default(),
+        receipts_trie: Default::default(),
+        storage_tries: vec![],
+    };
+    let load_all_mpts = KERNEL.global_labels["load_all_mpts"];
+    let mpt_delete_state_trie = KERNEL.global_labels["mpt_delete_state_trie"];
+    let mpt_hash_state_trie = KERNEL.global_labels["mpt_hash_state_trie"];
+
+    let initial_stack = vec![0xDEADBEEFu32.into()];
+    let mut interpreter = Interpreter::new_with_kernel(load_all_mpts, initial_stack);
+    interpreter.generation_state.mpt_prover_inputs = all_mpt_prover_inputs_reversed(&trie_inputs);
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    // Next, execute mpt_delete_state_trie.
+    interpreter.offset = mpt_delete_state_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.push(k.packed); // key
+    interpreter.push(k.count.into()); // num_nibbles
+
+    interpreter.run()?;
+    assert_eq!(
+        interpreter.stack().len(),
+        0,
+        "Expected empty stack after delete, found {:?}",
+        interpreter.stack()
+    );
+
+    // Now, execute mpt_hash_state_trie.
+    interpreter.offset = mpt_hash_state_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+
+    assert_eq!(
+        interpreter.stack().len(),
+        1,
+        "Expected 1 item on stack after hashing, found {:?}",
+        interpreter.stack()
+    );
+    let hash = H256::from_uint(&interpreter.stack()[0]);
+
+    let updated_trie = state_trie.delete(k);
+    let expected_state_trie_hash = updated_trie.calc_hash();
+    if hash != expected_state_trie_hash {
+        let trie_data = interpreter.get_trie_data();
+        let root_ptr =
+            interpreter.get_global_metadata_field(GlobalMetadata::StateTrieRoot).as_usize();
+        match diff_tries(trie_data, root_ptr, &updated_trie) {
+            Some(diff) => panic!("State trie hash mismatch: {}", diff),
+            None => assert_eq!(hash, expected_state_trie_hash),
+        }
+    }
+
+    Ok(())
+}
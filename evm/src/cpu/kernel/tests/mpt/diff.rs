@@ -0,0 +1,169 @@
+//! A debugging aid for test failures of the form `assert_eq!(hash,
+//! expected_hash)`: two 32-byte hashes differing says nothing about
+//! *where* the kernel's trie and the reference `PartialTrie` disagree.
+//! `diff_tries` walks both in lockstep from their roots and reports the
+//! deepest nibble-path at which they first diverge.
+
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{H256, U256};
+
+use crate::cpu::kernel::constants::trie_type::PartialTrieType;
+
+/// The first point at which a kernel-side trie (read out of `trie_data`)
+/// and a reference `PartialTrie` disagree.
+#[derive(Debug)]
+pub(crate) struct DiffPoint {
+    path: Nibbles,
+    local_type: String,
+    remote_type: String,
+}
+
+impl std::fmt::Display for DiffPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tries diverge at path {:#x}: left={}, right={}",
+            self.path.packed, self.local_type, self.remote_type
+        )
+    }
+}
+
+/// Compares the kernel's in-memory trie, rooted at `local_ptr` in
+/// `trie_data`, against the reference `remote` trie. Returns `None` if
+/// they represent the same trie, or the first `DiffPoint` at which they
+/// disagree (in nibble order across branch children).
+pub(crate) fn diff_tries(
+    trie_data: &[U256],
+    local_ptr: usize,
+    remote: &PartialTrie,
+) -> Option<DiffPoint> {
+    diff_tries_rec(trie_data, local_ptr, remote, Nibbles::default())
+}
+
+fn diff_tries_rec(
+    trie_data: &[U256],
+    local_ptr: usize,
+    remote: &PartialTrie,
+    path: Nibbles,
+) -> Option<DiffPoint> {
+    let local_type = PartialTrieType::from_u32(trie_data[local_ptr].as_u32());
+
+    match (local_type, remote) {
+        (PartialTrieType::Empty, PartialTrie::Empty) => None,
+        (PartialTrieType::Hash, PartialTrie::Hash(hash)) => {
+            let local_hash = read_hash(trie_data, local_ptr + 1);
+            if local_hash != *hash {
+                return Some(DiffPoint {
+                    path,
+                    local_type: format!("Hash({:#x})", local_hash),
+                    remote_type: format!("Hash({:#x})", hash),
+                });
+            }
+            None
+        }
+        (PartialTrieType::Leaf, PartialTrie::Leaf { nibbles, value }) => {
+            let local_nibbles = read_nibbles(trie_data, local_ptr + 1);
+            if local_nibbles != *nibbles {
+                return Some(DiffPoint {
+                    path,
+                    local_type: format!("Leaf(nibbles={:#x})", local_nibbles.packed),
+                    remote_type: format!("Leaf(nibbles={:#x})", nibbles.packed),
+                });
+            }
+            let local_value = read_value(trie_data, local_ptr + 3);
+            if local_value != *value {
+                return Some(DiffPoint {
+                    path,
+                    local_type: format!("Leaf(value={:?})", local_value),
+                    remote_type: format!("Leaf(value={:?})", value),
+                });
+            }
+            None
+        }
+        (PartialTrieType::Extension, PartialTrie::Extension { nibbles, child }) => {
+            let local_nibbles = read_nibbles(trie_data, local_ptr + 1);
+            if local_nibbles != *nibbles {
+                return Some(DiffPoint {
+                    path,
+                    local_type: format!("Extension(nibbles={:#x})", local_nibbles.packed),
+                    remote_type: format!("Extension(nibbles={:#x})", nibbles.packed),
+                });
+            }
+            let child_ptr = trie_data[local_ptr + 3].as_usize();
+            diff_tries_rec(trie_data, child_ptr, child, extend_path(&path, &nibbles))
+        }
+        (PartialTrieType::Branch, PartialTrie::Branch { children, value }) => {
+            let local_value = read_value(trie_data, local_ptr + 17);
+            if local_value != *value {
+                return Some(DiffPoint {
+                    path,
+                    local_type: format!("Branch(value={:?})", local_value),
+                    remote_type: format!("Branch(value={:?})", value),
+                });
+            }
+            for (nibble, child) in children.iter().enumerate() {
+                let child_ptr = trie_data[local_ptr + 1 + nibble].as_usize();
+                let child_path = extend_path_by_one(&path, nibble as u8);
+                if let Some(diff) = diff_tries_rec(trie_data, child_ptr, child, child_path) {
+                    return Some(diff);
+                }
+            }
+            None
+        }
+        (local_type, remote) => Some(DiffPoint {
+            path,
+            local_type: format!("{:?}", local_type),
+            remote_type: remote_kind(remote).to_string(),
+        }),
+    }
+}
+
+/// Reads the 32-byte hash a `Hash` node stores at `trie_data[ptr]`, in the
+/// same big-endian encoding `h256_to_u256` produces when the witness was
+/// serialized (see `generation::mpt_witness`).
+fn read_hash(trie_data: &[U256], ptr: usize) -> H256 {
+    let mut bytes = [0u8; 32];
+    trie_data[ptr].to_big_endian(&mut bytes);
+    H256(bytes)
+}
+
+fn read_nibbles(trie_data: &[U256], ptr: usize) -> Nibbles {
+    Nibbles {
+        count: trie_data[ptr].as_usize(),
+        packed: trie_data[ptr + 1],
+    }
+}
+
+fn read_value(trie_data: &[U256], ptr: usize) -> Vec<u8> {
+    let len = trie_data[ptr].as_usize();
+    trie_data[ptr + 1..ptr + 1 + len]
+        .iter()
+        .map(|word| word.as_u32() as u8)
+        .collect()
+}
+
+/// Appends `suffix`'s nibbles onto the end of `prefix`.
+fn extend_path(prefix: &Nibbles, suffix: &Nibbles) -> Nibbles {
+    Nibbles {
+        count: prefix.count + suffix.count,
+        packed: (prefix.packed << (4 * suffix.count)) | suffix.packed,
+    }
+}
+
+/// Appends a single nibble onto the end of `prefix`.
+fn extend_path_by_one(prefix: &Nibbles, nibble: u8) -> Nibbles {
+    Nibbles {
+        count: prefix.count + 1,
+        packed: (prefix.packed << 4) | U256::from(nibble),
+    }
+}
+
+fn remote_kind(trie: &PartialTrie) -> &'static str {
+    match trie {
+        PartialTrie::Empty => "Empty",
+        PartialTrie::Hash(_) => "Hash",
+        PartialTrie::Branch { .. } => "Branch",
+        PartialTrie::Extension { .. } => "Extension",
+        PartialTrie::Leaf { .. } => "Leaf",
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{BigEndianHash, H256};
+
+use super::nibbles;
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::mpt::{test_account_1_rlp, test_account_2_rlp};
+use crate::generation::mpt::all_mpt_prover_inputs_reversed;
+use crate::generation::TrieInputs;
+
+/// Builds a trie with two leaves, then replaces the untouched one (at
+/// nibble 0xB) with its precomputed hash, exercising the `Hash` node
+/// variant that lets a prover load only the part of the state trie a
+/// block's transactions actually touch.
+#[test]
+fn mpt_hash_with_hash_node() -> Result<()> {
+    let touched_leaf = PartialTrie::Leaf {
+        nibbles: nibbles(0xCD),
+        value: test_account_1_rlp(),
+    };
+    let untouched_leaf = PartialTrie::Leaf {
+        nibbles: nibbles(0xEF),
+        value: test_account_2_rlp(),
+    };
+
+    let mut full_children = std::array::from_fn(|_| PartialTrie::Empty.into());
+    full_children[0xA] = touched_leaf.clone().into();
+    full_children[0xB] = untouched_leaf.clone().into();
+    let full_trie = PartialTrie::Branch {
+        children: full_children,
+        value: vec![],
+    };
+    let expected_hash = full_trie.calc_hash();
+
+    let mut partial_children = std::array::from_fn(|_| PartialTrie::Empty.into());
+    partial_children[0xA] = touched_leaf.into();
+    partial_children[0xB] = PartialTrie::Hash(untouched_leaf.calc_hash()).into();
+    let partial_trie = PartialTrie::Branch {
+        children: partial_children,
+        value: vec![],
+    };
+
+    let hash = hash_state_trie(partial_trie)?;
+    assert_eq!(hash, expected_hash);
+
+    Ok(())
+}
+
+fn hash_state_trie(state_trie: PartialTrie) -> Result<H256> {
+    let trie_inputs = TrieInputs {
+        state_trie,
+        transactions_trie: Default::default(),
+        receipts_trie: Default::default(),
+        storage_tries: vec![],
+    };
+    let load_all_mpts = KERNEL.global_labels["load_all_mpts"];
+    let mpt_hash_state_trie = KERNEL.global_labels["mpt_hash_state_trie"];
+
+    let initial_stack = vec![0xDEADBEEFu32.into()];
+    let mut interpreter = Interpreter::new_with_kernel(load_all_mpts, initial_stack);
+    interpreter.generation_state.mpt_prover_inputs = all_mpt_prover_inputs_reversed(&trie_inputs);
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    interpreter.offset = mpt_hash_state_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+
+    assert_eq!(
+        interpreter.stack().len(),
+        1,
+        "Expected 1 item on stack after hashing, found {:?}",
+        interpreter.stack()
+    );
+    Ok(H256::from_uint(&interpreter.stack()[0]))
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{BigEndianHash, H256};
+
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::generation::mpt::all_mpt_prover_inputs_reversed;
+use crate::generation::TrieInputs;
+
+#[test]
+fn mpt_hash_txn_trie_empty() -> Result<()> {
+    test_ordered_trie(vec![], Trie::Txn)
+}
+
+#[test]
+fn mpt_hash_txn_trie_single_item() -> Result<()> {
+    test_ordered_trie(vec![vec![1, 2, 3]], Trie::Txn)
+}
+
+#[test]
+fn mpt_hash_txn_trie_many_items() -> Result<()> {
+    // 130 items so that the index-0x80 rollover (single-byte RLP prefixes
+    // give way to the two-byte `0x81 0x80` form once the index reaches
+    // 128) is exercised.
+    let items: Vec<Vec<u8>> = (0..130u64).map(|i| vec![i as u8; 4]).collect();
+    test_ordered_trie(items, Trie::Txn)
+}
+
+#[test]
+fn mpt_hash_receipt_trie_many_items() -> Result<()> {
+    let items: Vec<Vec<u8>> = (0..5u64).map(|i| vec![i as u8; 8]).collect();
+    test_ordered_trie(items, Trie::Receipt)
+}
+
+enum Trie {
+    Txn,
+    Receipt,
+}
+
+/// Builds an ordered trie the way the reference `ordered_trie_root` does
+/// (key `i` is the RLP encoding of the integer `i`, read as a nibble
+/// path), inserts the already-RLP-encoded `items` under those keys, and
+/// checks that the kernel's `mpt_hash_txn_trie`/`mpt_hash_receipt_trie`
+/// agrees with `PartialTrie::calc_hash`.
+fn test_ordered_trie(items: Vec<Vec<u8>>, which: Trie) -> Result<()> {
+    let mut trie = PartialTrie::Empty;
+    for (i, item) in items.iter().enumerate() {
+        trie = trie.insert(index_to_nibbles(i), item.clone());
+    }
+    let expected_hash = trie.calc_hash();
+
+    let mut trie_inputs = TrieInputs {
+        state_trie: Default::default(),
+        transactions_trie: Default::default(),
+        receipts_trie: Default::default(),
+        storage_tries: vec![],
+    };
+    match which {
+        Trie::Txn => trie_inputs.transactions_trie = trie,
+        Trie::Receipt => trie_inputs.receipts_trie = trie,
+    }
+
+    let load_all_mpts = KERNEL.global_labels["load_all_mpts"];
+    let hash_label = match which {
+        Trie::Txn => "mpt_hash_txn_trie",
+        Trie::Receipt => "mpt_hash_receipt_trie",
+    };
+    let mpt_hash_trie = KERNEL.global_labels[hash_label];
+
+    let initial_stack = vec![0xDEADBEEFu32.into()];
+    let mut interpreter = Interpreter::new_with_kernel(load_all_mpts, initial_stack);
+    interpreter.generation_state.mpt_prover_inputs = all_mpt_prover_inputs_reversed(&trie_inputs);
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    interpreter.offset = mpt_hash_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+
+    assert_eq!(
+        interpreter.stack().len(),
+        1,
+        "Expected 1 item on stack after hashing, found {:?}",
+        interpreter.stack()
+    );
+    let hash = H256::from_uint(&interpreter.stack()[0]);
+    assert_eq!(hash, expected_hash);
+
+    Ok(())
+}
+
+/// The key for item `i` in an ordered trie is `rlp::encode(&i)`, read as a
+/// nibble path: index 0 -> key byte 0x80, index 1 -> 0x01, ..., index 127
+/// -> 0x7f, index 128 -> 0x81 0x80, and so on.
+fn index_to_nibbles(i: usize) -> Nibbles {
+    let bytes = rlp::encode(&(i as u64));
+    Nibbles::from_bytes_be(&bytes).expect("RLP of a u64 always fits in the nibble path")
+}
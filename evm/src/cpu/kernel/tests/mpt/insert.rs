@@ -2,6 +2,7 @@ use anyhow::Result;
 use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
 use ethereum_types::{BigEndianHash, H256};
 
+use super::diff::diff_tries;
 use super::nibbles;
 use crate::cpu::kernel::aggregator::KERNEL;
 use crate::cpu::kernel::constants::global_metadata::GlobalMetadata;
@@ -189,7 +190,15 @@ fn test_state_trie(state_trie: PartialTrie, k: Nibbles, v: Vec<u8>) -> Result<()
 
     let updated_trie = state_trie.insert(k, v);
     let expected_state_trie_hash = updated_trie.calc_hash();
-    assert_eq!(hash, expected_state_trie_hash);
+    if hash != expected_state_trie_hash {
+        let trie_data = interpreter.get_trie_data();
+        let root_ptr =
+            interpreter.get_global_metadata_field(GlobalMetadata::StateTrieRoot).as_usize();
+        match diff_tries(trie_data, root_ptr, &updated_trie) {
+            Some(diff) => panic!("State trie hash mismatch: {}", diff),
+            None => assert_eq!(hash, expected_state_trie_hash),
+        }
+    }
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{BigEndianHash, H256, U256};
+
+use super::nibbles;
+use crate::cpu::kernel::aggregator::KERNEL;
+use crate::cpu::kernel::interpreter::Interpreter;
+use crate::cpu::kernel::tests::mpt::{test_account_1_rlp, test_account_2_rlp};
+use crate::generation::mpt_witness::{mpt_witness_prover_inputs_reversed, TrieNode, WitnessInputs};
+
+fn example_trie() -> PartialTrie {
+    let mut children = std::array::from_fn(|_| PartialTrie::Empty.into());
+    children[0xA] = PartialTrie::Leaf {
+        nibbles: nibbles(0xBC),
+        value: test_account_1_rlp(),
+    }
+    .into();
+    children[0xD] = PartialTrie::Leaf {
+        nibbles: nibbles(0xEF),
+        value: test_account_2_rlp(),
+    }
+    .into();
+    PartialTrie::Branch {
+        children,
+        value: vec![],
+    }
+}
+
+/// Replaces every sibling not on the path to `key` with its hash, leaving
+/// only the nodes a Merkle proof for `key` would actually need to open.
+fn witness_along(trie: &PartialTrie, key: Nibbles) -> TrieNode {
+    match trie {
+        PartialTrie::Branch { children, value } => {
+            if key.count == 0 {
+                return PartialTrie::Branch {
+                    children: std::array::from_fn(|i| {
+                        if children[i].as_ref().calc_hash() == PartialTrie::Empty.calc_hash() {
+                            PartialTrie::Empty.into()
+                        } else {
+                            PartialTrie::Hash(children[i].as_ref().calc_hash()).into()
+                        }
+                    }),
+                    value: value.clone(),
+                };
+            }
+            let rest_bits = 4 * (key.count - 1);
+            let nibble = ((key.packed >> rest_bits).low_u32() & 0xF) as usize;
+            let rest = Nibbles {
+                count: key.count - 1,
+                packed: key.packed & ((U256::one() << rest_bits) - U256::one()),
+            };
+            PartialTrie::Branch {
+                children: std::array::from_fn(|i| {
+                    if i == nibble {
+                        witness_along(&children[i], rest).into()
+                    } else if children[i].as_ref().calc_hash() == PartialTrie::Empty.calc_hash() {
+                        PartialTrie::Empty.into()
+                    } else {
+                        PartialTrie::Hash(children[i].as_ref().calc_hash()).into()
+                    }
+                }),
+                value: value.clone(),
+            }
+        }
+        other => other.clone(),
+    }
+}
+
+#[test]
+fn load_mpts_from_witness_accepts_valid_proof() -> Result<()> {
+    let trie = example_trie();
+    let root = trie.calc_hash();
+    let key = nibbles(0xABC);
+    let value = test_account_1_rlp();
+
+    let witness = WitnessInputs {
+        state_root: root,
+        accesses: vec![(key, value, vec![witness_along(&trie, key)])],
+    };
+
+    let load_mpts_from_witness = KERNEL.global_labels["load_mpts_from_witness"];
+    let mpt_hash_state_trie = KERNEL.global_labels["mpt_hash_state_trie"];
+
+    let initial_stack = vec![0xDEADBEEFu32.into()];
+    let mut interpreter = Interpreter::new_with_kernel(load_mpts_from_witness, initial_stack);
+    interpreter.generation_state.mpt_prover_inputs = mpt_witness_prover_inputs_reversed(&witness);
+    interpreter.run()?;
+    assert_eq!(interpreter.stack(), vec![]);
+
+    interpreter.offset = mpt_hash_state_trie;
+    interpreter.push(0xDEADBEEFu32.into());
+    interpreter.run()?;
+    let hash = H256::from_uint(&interpreter.stack()[0]);
+    assert_eq!(hash, root);
+
+    Ok(())
+}
+
+#[test]
+fn load_mpts_from_witness_rejects_tampered_node() -> Result<()> {
+    let trie = example_trie();
+    let root = trie.calc_hash();
+    let key = nibbles(0xABC);
+    let value = test_account_1_rlp();
+
+    let mut tampered = witness_along(&trie, key);
+    if let PartialTrie::Branch { value, .. } = &mut tampered {
+        // Corrupt the branch's value so the reconstructed trie no longer
+        // hashes to the asserted root.
+        *value = vec![0xFF];
+    }
+
+    let witness = WitnessInputs {
+        state_root: root,
+        accesses: vec![(key, value, vec![tampered])],
+    };
+
+    let load_mpts_from_witness = KERNEL.global_labels["load_mpts_from_witness"];
+    let initial_stack = vec![0xDEADBEEFu32.into()];
+    let mut interpreter = Interpreter::new_with_kernel(load_mpts_from_witness, initial_stack);
+    interpreter.generation_state.mpt_prover_inputs = mpt_witness_prover_inputs_reversed(&witness);
+
+    assert!(
+        interpreter.run().is_err(),
+        "expected the root check to reject a tampered witness node"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use eth_trie_utils::partial_trie::Nibbles;
+use ethereum_types::U256;
+
+use crate::generation::mpt::AccountRlp;
+
+mod delete;
+mod diff;
+mod hash;
+mod hash_ordered_trie;
+mod insert;
+mod load_witness;
+
+pub(crate) fn nibbles(packed: u64) -> Nibbles {
+    let bit_len = 64 - packed.leading_zeros() as usize;
+    let count = (bit_len + 3) / 4;
+    Nibbles {
+        count,
+        packed: packed.into(),
+    }
+}
+
+pub(crate) fn test_account_1() -> AccountRlp {
+    AccountRlp {
+        nonce: U256::from(1111),
+        balance: U256::from(2222),
+        storage_root: U256::from(3333).into(),
+        code_hash: U256::from(4444).into(),
+    }
+}
+
+pub(crate) fn test_account_1_rlp() -> Vec<u8> {
+    rlp::encode(&test_account_1()).to_vec()
+}
+
+pub(crate) fn test_account_2() -> AccountRlp {
+    AccountRlp {
+        nonce: U256::from(5555),
+        balance: U256::from(6666),
+        storage_root: U256::from(7777).into(),
+        code_hash: U256::from(8888).into(),
+    }
+}
+
+pub(crate) fn test_account_2_rlp() -> Vec<u8> {
+    rlp::encode(&test_account_2()).to_vec()
+}
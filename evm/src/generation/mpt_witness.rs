@@ -0,0 +1,84 @@
+use eth_trie_utils::partial_trie::{Nibbles, PartialTrie};
+use ethereum_types::{H256, U256};
+
+use crate::cpu::kernel::constants::trie_type::PartialTrieType;
+
+/// A node encountered while walking a Merkle proof down to some key. This
+/// is the same shape as `PartialTrie` (including its `Hash` variant for
+/// subtrees the proof didn't need to open), so no conversion is needed
+/// between "the trie the host built" and "the witness nodes it hands to
+/// the kernel".
+pub type TrieNode = PartialTrie;
+
+/// An alternative to [`TrieInputs`](super::TrieInputs) that avoids
+/// serializing an entire state trie into the witness: the host instead
+/// asserts the pre-state root directly, and supplies, for each account or
+/// storage slot the block touches, the key/value pair together with the
+/// Merkle proof nodes needed to authenticate it against that root. The
+/// kernel reconstructs just the touched part of the trie from those
+/// nodes, checks it hashes to `state_root`, and only then proceeds —
+/// shrinking per-block in-circuit work from "hash the whole trie" to
+/// "verify a root plus look up a handful of values".
+#[derive(Clone, Debug, Default)]
+pub struct WitnessInputs {
+    pub state_root: H256,
+    pub accesses: Vec<(Nibbles, Vec<u8>, Vec<TrieNode>)>,
+}
+
+/// Serializes `witness` into the same kind of prover-input tape that
+/// `all_mpt_prover_inputs_reversed` produces for a full `TrieInputs`,
+/// except consumed by `load_mpts_from_witness` instead of `load_all_mpts`.
+/// As with the full-trie loader, the kernel reads the tape front-to-back,
+/// so the returned vector is reversed (it's popped from the end).
+pub fn mpt_witness_prover_inputs_reversed(witness: &WitnessInputs) -> Vec<U256> {
+    let mut inputs = vec![h256_to_u256(witness.state_root), witness.accesses.len().into()];
+    for (key, value, nodes) in &witness.accesses {
+        inputs.push(key.count.into());
+        inputs.push(key.packed);
+        push_bytes(&mut inputs, value);
+        inputs.push(nodes.len().into());
+        for node in nodes {
+            serialize_node(node, &mut inputs);
+        }
+    }
+    inputs.reverse();
+    inputs
+}
+
+fn serialize_node(node: &TrieNode, inputs: &mut Vec<U256>) {
+    match node {
+        PartialTrie::Empty => inputs.push((PartialTrieType::Empty as u32).into()),
+        PartialTrie::Hash(hash) => {
+            inputs.push((PartialTrieType::Hash as u32).into());
+            inputs.push(h256_to_u256(*hash));
+        }
+        PartialTrie::Leaf { nibbles, value } => {
+            inputs.push((PartialTrieType::Leaf as u32).into());
+            inputs.push(nibbles.count.into());
+            inputs.push(nibbles.packed);
+            push_bytes(inputs, value);
+        }
+        PartialTrie::Extension { nibbles, child } => {
+            inputs.push((PartialTrieType::Extension as u32).into());
+            inputs.push(nibbles.count.into());
+            inputs.push(nibbles.packed);
+            serialize_node(child, inputs);
+        }
+        PartialTrie::Branch { children, value } => {
+            inputs.push((PartialTrieType::Branch as u32).into());
+            for child in children {
+                serialize_node(child, inputs);
+            }
+            push_bytes(inputs, value);
+        }
+    }
+}
+
+fn h256_to_u256(hash: H256) -> U256 {
+    U256::from_big_endian(hash.as_bytes())
+}
+
+fn push_bytes(inputs: &mut Vec<U256>, bytes: &[u8]) {
+    inputs.push(bytes.len().into());
+    inputs.extend(bytes.iter().map(|&b| U256::from(b)));
+}